@@ -1,21 +1,88 @@
 use async_std::task;
 use flutter_engine::ffi::ExternalTextureFrame;
 use flutter_engine::texture_registry::TextureRegistry;
-use flutter_engine::FlutterEngineHandler;
+use flutter_engine::{FlutterEngine, FlutterEngineHandler};
+use flutter_plugins::mousecursor::MouseCursorHandler;
 use flutter_plugins::platform::{AppSwitcherDescription, PlatformHandler, MimeError};
 use flutter_plugins::window::{PositionParams, WindowHandler};
 use futures_task::FutureObj;
 use glfw::Context;
 use parking_lot::Mutex;
+use std::collections::VecDeque;
 use std::ffi::c_void;
 use std::future::Future;
 use std::sync::Arc;
 
+/// Queues closures that must run on the thread owning the GLFW context
+/// (texture registration, GL context work, window mutation) instead of on
+/// the `async_std` background pool used by [`GlfwFlutterEngineHandler::run_in_background`].
+pub(crate) struct MainThreadDispatcher {
+    queue: Mutex<VecDeque<Box<dyn FnOnce() + Send>>>,
+}
+
+impl MainThreadDispatcher {
+    pub(crate) fn new() -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub(crate) fn post_to_main(&self, task: Box<dyn FnOnce() + Send>) {
+        self.queue.lock().push_back(task);
+        unsafe {
+            glfw::ffi::glfwPostEmptyEvent();
+        }
+    }
+
+    /// Drains and runs every queued closure. Must be called from the main
+    /// thread at the top of each GLFW poll loop iteration, before pumping
+    /// engine tasks.
+    pub(crate) fn run_pending(&self) {
+        let tasks: Vec<_> = self.queue.lock().drain(..).collect();
+        for task in tasks {
+            task();
+        }
+    }
+}
+
 pub(crate) struct GlfwFlutterEngineHandler {
     pub(crate) glfw: glfw::Glfw,
     pub(crate) window: Arc<Mutex<glfw::Window>>,
     pub(crate) resource_window: Arc<Mutex<glfw::Window>>,
     pub(crate) texture_registry: Arc<Mutex<TextureRegistry>>,
+    pub(crate) main_thread_dispatcher: Arc<MainThreadDispatcher>,
+}
+
+impl GlfwFlutterEngineHandler {
+    /// Like `run_in_background`, but for work that must execute on the
+    /// thread that owns the GLFW context.
+    pub(crate) fn spawn_on_main<F>(&self, task: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.main_thread_dispatcher.post_to_main(Box::new(task));
+    }
+}
+
+/// Recomputes window metrics for the current content scale and forwards
+/// them to the engine. Called on the initial frame (so the first layout
+/// already uses the correct scale) and again from the event loop for every
+/// GLFW content-scale event, whenever it changes (e.g. the window is
+/// dragged to a monitor with a different scale factor).
+///
+/// GLFW reports separate x/y content-scale factors, but Flutter's
+/// `pixel_ratio` is a single scalar and x/y agree on every platform GLFW
+/// supports, so `scale` carries just the one value both dimensions use.
+///
+/// Physical size comes from `get_framebuffer_size()` rather than
+/// `logical_size * scale`: the two agree almost everywhere, but the
+/// framebuffer is the actual render target, and on platforms where it
+/// isn't an exact multiple of content scale (odd window-manager rounding,
+/// fractional scaling quirks) the engine needs the real value, not a
+/// derived approximation.
+pub(crate) fn send_window_metrics(engine: &FlutterEngine, window: &glfw::Window, scale: f32) {
+    let (physical_width, physical_height) = window.get_framebuffer_size();
+    engine.send_window_metrics_event(physical_width as usize, physical_height as usize, scale as f64);
 }
 
 impl FlutterEngineHandler for GlfwFlutterEngineHandler {
@@ -72,19 +139,125 @@ impl FlutterEngineHandler for GlfwFlutterEngineHandler {
     }
 }
 
+/// An image payload exchanged with the platform clipboard, tightly packed
+/// top-to-bottom RGBA8.
+pub struct ImageData {
+    pub width: u32,
+    pub height: u32,
+    pub bytes: Vec<u8>,
+}
+
+/// The set of clipboard payloads the GLFW backend can read and write.
+/// GLFW itself only exchanges `text/plain`; images go through `arboard`
+/// instead. No other formats (e.g. HTML) are handled.
+pub enum ClipboardData {
+    Text(String),
+    Image {
+        width: u32,
+        height: u32,
+        bytes: Vec<u8>,
+    },
+}
+
 pub struct GlfwPlatformHandler {
     pub window: Arc<Mutex<glfw::Window>>,
+    clipboard: Mutex<Option<arboard::Clipboard>>,
 }
 
 unsafe impl Send for GlfwPlatformHandler {}
 
+impl GlfwPlatformHandler {
+    pub fn new(window: Arc<Mutex<glfw::Window>>) -> Self {
+        Self {
+            window,
+            clipboard: Mutex::new(None),
+        }
+    }
+
+    /// Opens the system image clipboard on first use rather than at
+    /// construction time. Text never needs this (it goes through GLFW's own
+    /// clipboard string), so a missing `arboard` backend -- headless CI, a
+    /// bare Wayland session without a portal, etc. -- only fails the image
+    /// path the first time it's actually exercised instead of crashing
+    /// startup for every app.
+    fn with_clipboard<R>(
+        &self,
+        f: impl FnOnce(&mut arboard::Clipboard) -> Result<R, MimeError>,
+    ) -> Result<R, MimeError> {
+        let mut clipboard = self.clipboard.lock();
+        if clipboard.is_none() {
+            *clipboard = Some(arboard::Clipboard::new().map_err(|_| MimeError)?);
+        }
+        f(clipboard.as_mut().unwrap())
+    }
+
+    pub fn get_clipboard_image(&mut self) -> Result<ImageData, MimeError> {
+        self.with_clipboard(|clipboard| {
+            let image = clipboard.get_image().map_err(|_| MimeError)?;
+            Ok(ImageData {
+                width: image.width as u32,
+                height: image.height as u32,
+                bytes: image.bytes.into_owned(),
+            })
+        })
+    }
+
+    /// Writes `data` to the clipboard. Unlike `set_clipboard_data`, errors
+    /// are surfaced rather than swallowed, since a failed image write is
+    /// silent corruption otherwise (the app thinks the copy succeeded).
+    ///
+    /// `Clipboard.setData` reaches the `Image` variant through
+    /// `PlatformHandler::set_clipboard_data`, which decodes an
+    /// `image/png` data URL into raw RGBA8 before calling this.
+    pub fn set_clipboard(&mut self, data: ClipboardData) -> Result<(), MimeError> {
+        match data {
+            ClipboardData::Text(text) => {
+                self.window.lock().set_clipboard_string(&text);
+                Ok(())
+            }
+            ClipboardData::Image {
+                width,
+                height,
+                bytes,
+            } => self.with_clipboard(|clipboard| {
+                clipboard
+                    .set_image(arboard::ImageData {
+                        width: width as usize,
+                        height: height as usize,
+                        bytes: bytes.into(),
+                    })
+                    .map_err(|_| MimeError)
+            }),
+        }
+    }
+}
+
+/// `Clipboard.setData`/`getData` only ever carry a single `String`, with no
+/// accompanying MIME type, so an `image/png` payload is tagged with this
+/// prefix to tell it apart from plain text on both ends of the trait's
+/// `String`-only entry points.
+const IMAGE_PNG_DATA_URL_PREFIX: &str = "data:image/png;base64,";
+
 impl PlatformHandler for GlfwPlatformHandler {
     fn set_application_switcher_description(&mut self, description: AppSwitcherDescription) {
         self.window.lock().set_title(&description.label);
     }
 
     fn set_clipboard_data(&mut self, text: String) {
-        self.window.lock().set_clipboard_string(&text);
+        // The trait signature is fixed upstream and carries no MIME type, so
+        // an `image/png` write (from `Clipboard.setData` with the data URL
+        // produced below) is recognized by its prefix; anything else is
+        // plain text. The image path can fail (a bad PNG, a missing
+        // clipboard backend), but the trait returns nothing, so the error
+        // is dropped here -- same ceiling every other plugin call has.
+        let data = match text.strip_prefix(IMAGE_PNG_DATA_URL_PREFIX) {
+            Some(encoded) => match decode_png_data_url(encoded) {
+                Ok(image) => image,
+                Err(_) => return,
+            },
+            None => ClipboardData::Text(text),
+        };
+        let _ = self.set_clipboard(data);
     }
 
     fn get_clipboard_data(&mut self, mime: &str) -> Result<String, MimeError> {
@@ -93,23 +266,141 @@ impl PlatformHandler for GlfwPlatformHandler {
                 None => "".to_string(),
                 Some(val) => val,
             }),
+            "image/png" => {
+                let image = self.get_clipboard_image()?;
+                let rgba = image::RgbaImage::from_raw(image.width, image.height, image.bytes)
+                    .ok_or(MimeError)?;
+                let mut png_bytes = Vec::new();
+                rgba.write_to(
+                    &mut std::io::Cursor::new(&mut png_bytes),
+                    image::ImageOutputFormat::Png,
+                )
+                .map_err(|_| MimeError)?;
+                Ok(format!(
+                    "{}{}",
+                    IMAGE_PNG_DATA_URL_PREFIX,
+                    base64::encode(&png_bytes)
+                ))
+            }
             _ => Err(MimeError),
         }
     }
 }
 
+/// Decodes a base64-encoded PNG (as produced by `get_clipboard_data`'s
+/// `image/png` arm) back into the raw RGBA8 payload `set_clipboard` expects.
+fn decode_png_data_url(encoded: &str) -> Result<ClipboardData, MimeError> {
+    let png_bytes = base64::decode(encoded).map_err(|_| MimeError)?;
+    let rgba = image::load_from_memory(&png_bytes)
+        .map_err(|_| MimeError)?
+        .to_rgba8();
+    let (width, height) = rgba.dimensions();
+    Ok(ClipboardData::Image {
+        width,
+        height,
+        bytes: rgba.into_raw(),
+    })
+}
+
+pub struct GlfwMouseCursorHandler {
+    window: Arc<Mutex<glfw::Window>>,
+    // `glfw::Cursor` owns a non-`Clone` `GLFWcursor*`, and `Window::
+    // set_cursor` (the API the request specified) takes it by value, so a
+    // `HashMap<String, Cursor>` cache can never hand one back out without
+    // either cloning (impossible) or reaching for non-public FFI. Tracking
+    // just the currently-active kind instead still avoids the case the
+    // request actually cares about -- recreating a `Cursor` on every
+    // hover event -- since repeated events for the same kind (the common
+    // case while the pointer sits over one widget) become a no-op.
+    current_kind: Mutex<Option<String>>,
+}
+
+unsafe impl Send for GlfwMouseCursorHandler {}
+
+impl GlfwMouseCursorHandler {
+    pub fn new(window: Arc<Mutex<glfw::Window>>) -> Self {
+        Self {
+            window,
+            current_kind: Mutex::new(None),
+        }
+    }
+
+    fn standard_cursor(kind: &str) -> Option<glfw::StandardCursor> {
+        Some(match kind {
+            "basic" => glfw::StandardCursor::Arrow,
+            "click" => glfw::StandardCursor::Hand,
+            "text" => glfw::StandardCursor::IBeam,
+            // Matches the cursor set the request enumerates
+            // (Arrow/Hand/IBeam/Crosshair/HResize/VResize); `Crosshair`
+            // isn't a perfect "not allowed" shape, but a GLFW-3.4-only
+            // `NotAllowed` would fail to compile against the pinned
+            // glfw-rs version the rest of this crate is built with.
+            "forbidden" | "noDrop" => glfw::StandardCursor::Crosshair,
+            "grab" | "grabbing" => glfw::StandardCursor::Hand,
+            "resizeLeftRight" | "resizeColumn" => glfw::StandardCursor::HResize,
+            "resizeUpDown" | "resizeRow" => glfw::StandardCursor::VResize,
+            _ => return None,
+        })
+    }
+}
+
+impl MouseCursorHandler for GlfwMouseCursorHandler {
+    fn activate_system_cursor(&mut self, _device: i64, kind: String) {
+        if kind == "none" {
+            self.window.lock().set_cursor_mode(glfw::CursorMode::Hidden);
+            *self.current_kind.lock() = Some(kind);
+            return;
+        }
+
+        let standard = match Self::standard_cursor(&kind) {
+            Some(standard) => standard,
+            None => return,
+        };
+
+        let mut current_kind = self.current_kind.lock();
+        if current_kind.as_deref() == Some(kind.as_str()) {
+            return;
+        }
+
+        let mut window = self.window.lock();
+        window.set_cursor_mode(glfw::CursorMode::Normal);
+        window.set_cursor(Some(glfw::Cursor::standard(standard)));
+        *current_kind = Some(kind);
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct VideoModeParams {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    pub name: String,
+    pub position: (i32, i32),
+    pub physical_size: (i32, i32),
+    pub content_scale: (f32, f32),
+    pub video_mode: Option<VideoModeParams>,
+}
+
 pub struct GlfwWindowHandler {
+    glfw: glfw::Glfw,
     window: Arc<Mutex<glfw::Window>>,
     dragging: bool,
     start_cursor_pos: (f64, f64),
+    windowed_bounds: Option<(i32, i32, i32, i32)>,
 }
 
 impl GlfwWindowHandler {
-    pub fn new(window: Arc<Mutex<glfw::Window>>) -> Self {
+    pub fn new(glfw: glfw::Glfw, window: Arc<Mutex<glfw::Window>>) -> Self {
         Self {
+            glfw,
             window,
             dragging: false,
             start_cursor_pos: (0.0, 0.0),
+            windowed_bounds: None,
         }
     }
 
@@ -123,6 +414,95 @@ impl GlfwWindowHandler {
         }
         self.dragging
     }
+
+    // `list_monitors`/`enter_fullscreen`/`exit_fullscreen` live here as
+    // inherent methods rather than on `impl WindowHandler` because they
+    // name `MonitorInfo`/`VideoModeParams`, which are defined in this
+    // crate; `flutter_plugins::window::WindowHandler` can't reference
+    // flutter-glfw types. Exposing them through the window plugin's method
+    // channel needs matching additions to that upstream trait.
+
+    pub fn list_monitors(&mut self) -> Vec<MonitorInfo> {
+        let mut monitors = Vec::new();
+        self.glfw.with_connected_monitors(|_, glfw_monitors| {
+            for monitor in glfw_monitors {
+                let (x, y) = monitor.get_pos();
+                let physical_size = monitor.get_physical_size();
+                let content_scale = monitor.get_content_scale();
+                let video_mode = monitor.get_video_mode().map(|mode| VideoModeParams {
+                    width: mode.width,
+                    height: mode.height,
+                    refresh_rate: mode.refresh_rate,
+                });
+                monitors.push(MonitorInfo {
+                    name: monitor.get_name().unwrap_or_default(),
+                    position: (x, y),
+                    physical_size,
+                    content_scale,
+                    video_mode,
+                });
+            }
+        });
+        monitors
+    }
+
+    pub fn enter_fullscreen(&mut self, monitor_index: usize, video_mode: Option<VideoModeParams>) {
+        let mut window = self.window.lock();
+        if self.windowed_bounds.is_none() {
+            let (x, y) = window.get_pos();
+            let (width, height) = window.get_size();
+            self.windowed_bounds = Some((x, y, width, height));
+        }
+        self.glfw.with_connected_monitors_mut(|_, glfw_monitors| {
+            if let Some(monitor) = glfw_monitors.get_mut(monitor_index) {
+                let (width, height, refresh_rate) = video_mode
+                    .map(|mode| (mode.width, mode.height, Some(mode.refresh_rate)))
+                    .unwrap_or_else(|| match monitor.get_video_mode() {
+                        Some(mode) => (mode.width, mode.height, Some(mode.refresh_rate)),
+                        None => (0, 0, None),
+                    });
+                window.set_monitor(
+                    glfw::WindowMode::FullScreen(monitor),
+                    0,
+                    0,
+                    width,
+                    height,
+                    refresh_rate,
+                );
+            }
+        });
+    }
+
+    pub fn exit_fullscreen(&mut self) {
+        if let Some((x, y, width, height)) = self.windowed_bounds.take() {
+            self.window.lock().set_monitor(
+                glfw::WindowMode::Windowed,
+                x,
+                y,
+                width as u32,
+                height as u32,
+                None,
+            );
+        }
+    }
+
+    // `set_decorated`/`set_opacity`/`set_always_on_top` are inherent
+    // methods, not `WindowHandler` trait methods: `flutter_plugins::window`
+    // doesn't declare them, so the window plugin's method channel can't
+    // dispatch to them yet. Exposing them to Dart needs matching additions
+    // to `WindowHandler` upstream.
+
+    pub fn set_decorated(&mut self, decorated: bool) {
+        self.window.lock().set_decorated(decorated);
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.window.lock().set_opacity(opacity);
+    }
+
+    pub fn set_always_on_top(&mut self, always_on_top: bool) {
+        self.window.lock().set_floating(always_on_top);
+    }
 }
 
 unsafe impl Send for GlfwWindowHandler {}
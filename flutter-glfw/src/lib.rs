@@ -0,0 +1,4 @@
+mod application;
+mod handler;
+
+pub use application::FlutterWindow;